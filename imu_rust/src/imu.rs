@@ -1,15 +1,23 @@
+use std::collections::VecDeque;
+use std::thread;
 use std::time::{Duration, Instant};
 
 use bno080::interface::{I2cInterface, SensorInterface};
 use bno080::Error as BnoError;
 use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::blocking::i2c::{Read as I2cRead, Write as I2cWrite, WriteRead as I2cWriteRead};
+use embedded_hal::digital::v2::{InputPin, OutputPin};
 use linux_embedded_hal::i2cdev::linux::LinuxI2CError;
-use linux_embedded_hal::{Delay, I2cdev};
+use linux_embedded_hal::sysfs_gpio::Direction as GpioDirection;
+use linux_embedded_hal::{Delay, I2cdev, Pin as GpioPin};
+use smallvec::SmallVec;
 use thiserror::Error;
 
 const PACKET_HEADER_LENGTH: usize = 4;
 const PACKET_SEND_BUF_LEN: usize = 256;
 const PACKET_RECV_BUF_LEN: usize = 1024;
+const FRAME_QUEUE_CAPACITY: usize = 32;
+const EVENT_QUEUE_CAPACITY: usize = 32;
 const NUM_CHANNELS: usize = 6;
 
 const CHANNEL_COMMAND: u8 = 0;
@@ -25,9 +33,13 @@ const EXECUTABLE_DEVICE_RESP_RESET_COMPLETE: u8 = 0x01;
 
 const SHUB_PROD_ID_REQ: u8 = 0xF9;
 const SHUB_PROD_ID_RESP: u8 = 0xF8;
+const SHUB_COMMAND_REQ: u8 = 0xF2;
 const SHUB_COMMAND_RESP: u8 = 0xF1;
 const SHUB_REPORT_SET_FEATURE_CMD: u8 = 0xFD;
 
+const SH2_CMD_SAVE_DCD: u8 = 0x06;
+const SH2_CMD_ME_CALIBRATION: u8 = 0x07;
+
 const SH2_CMD_INITIALIZE: u8 = 0x04;
 const SH2_INIT_UNSOLICITED: u8 = 0x80;
 const SH2_INIT_SYSTEM: u8 = 0x01;
@@ -38,6 +50,30 @@ const SENSOR_REPORTID_GYRO_CALIBRATED: u8 = 0x02;
 const SENSOR_REPORTID_LINEAR_ACCELERATION: u8 = 0x04;
 const SENSOR_REPORTID_ROTATION_VECTOR: u8 = 0x05;
 const SENSOR_REPORTID_GAME_ROTATION_VECTOR: u8 = 0x08;
+const SENSOR_REPORTID_STEP_COUNTER: u8 = 0x11;
+const SENSOR_REPORTID_STABILITY_CLASSIFIER: u8 = 0x13;
+const SENSOR_REPORTID_TAP_DETECTOR: u8 = 0x10;
+const SENSOR_REPORTID_STEP_DETECTOR: u8 = 0x18;
+const SENSOR_REPORTID_PERSONAL_ACTIVITY_CLASSIFIER: u8 = 0x1E;
+const ACTIVITY_STATE_COUNT: usize = 9;
+
+const SENSOR_REPORT_BASE_TIMESTAMP: u8 = 0xFB;
+const TIMESTAMP_TICK_US: i64 = 100;
+
+const SHUB_FRS_WRITE_REQUEST: u8 = 0xF7;
+const SHUB_FRS_WRITE_DATA: u8 = 0xF6;
+const SHUB_FRS_WRITE_RESPONSE: u8 = 0xF5;
+const SHUB_FRS_READ_REQUEST: u8 = 0xF4;
+const SHUB_FRS_READ_RESPONSE: u8 = 0xF3;
+
+const FRS_WRITE_STATUS_RECEIVED: u8 = 0;
+const FRS_WRITE_STATUS_READY: u8 = 4;
+const FRS_WRITE_STATUS_COMPLETED: u8 = 3;
+const FRS_READ_STATUS_BUSY: u8 = 2;
+const FRS_READ_STATUS_NO_MORE_DATA: u8 = 3;
+const FRS_READ_STATUS_COMPLETE: u8 = 4;
+const FRS_READ_STATUS_BLOCK_COMPLETE: u8 = 5;
+const FRS_READ_STATUS_RECORD_EMPTY: u8 = 7;
 
 const Q8_SCALE: f32 = 1.0 / (1 << 8) as f32;
 const Q9_SCALE: f32 = 1.0 / (1 << 9) as f32;
@@ -56,6 +92,78 @@ pub struct Frame {
     pub qi: f32,
     pub qj: f32,
     pub qk: f32,
+    /// Worst-case calibration accuracy (SH-2 scale) across the contributing reports:
+    /// 0 = unreliable, 1 = low, 2 = medium, 3 = high.
+    pub accuracy: u8,
+}
+
+/// Discrete on-chip gesture/classifier events, consumed alongside the continuous `Frame`
+/// stream via [`Imu::poll_event`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImuEvent {
+    Tap(TapEvent),
+    Step,
+    StepCount(u16),
+    Stability(StabilityState),
+    Activity(ActivityEvent),
+}
+
+/// Bitfield reported by the BNO08x tap detector (report id `0x10`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TapEvent {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+    pub double_tap: bool,
+}
+
+impl TapEvent {
+    fn from_bitfield(bits: u8) -> Self {
+        Self {
+            x: bits & 0x03 != 0,
+            y: bits & 0x0C != 0,
+            z: bits & 0x30 != 0,
+            double_tap: bits & 0x40 != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StabilityState {
+    Unknown,
+    OnTable,
+    Stationary,
+    Stable,
+    Motion,
+}
+
+impl StabilityState {
+    fn from_report_value(value: u8) -> Self {
+        match value {
+            1 => Self::OnTable,
+            2 => Self::Stationary,
+            3 => Self::Stable,
+            4 => Self::Motion,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Most-likely personal-activity state plus per-state confidence (0-100) reported by the
+/// classifier (report id `0x1E`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityEvent {
+    pub most_likely: u8,
+    pub confidences: Vec<u8>,
+}
+
+/// Selects how `Imu::init` talks to the bus: the kernel I2C driver, or a software
+/// bit-banged implementation driving SCL/SDA as raw GPIO lines (see [`BitbangI2c`])
+/// for boards where `/dev/i2c-N` is unreliable or missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I2cBackendKind {
+    Hardware,
+    Bitbang { scl_pin: u64, sda_pin: u64 },
 }
 
 #[derive(Debug, Clone)]
@@ -63,12 +171,112 @@ pub struct ImuConfig {
     pub bus: u8,
     pub address: u8,
     pub hz: u16,
+    pub i2c: I2cBackendKind,
+}
+
+impl Default for ImuConfig {
+    fn default() -> Self {
+        Self {
+            bus: 1,
+            address: 0x4A,
+            hz: 100,
+            i2c: I2cBackendKind::Hardware,
+        }
+    }
 }
 
 impl ImuConfig {
     pub fn device_path(&self) -> String {
         format!("/dev/i2c-{}", self.bus)
     }
+
+    /// Loads a config from a simple `key=value`-per-line text file (keys `bus`, `addr`,
+    /// `hz`), skipping blank lines and `#` comments. Missing keys fall back to defaults.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        contents.parse()
+    }
+}
+
+impl std::str::FromStr for ImuConfig {
+    type Err = ConfigError;
+
+    fn from_str(input: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        for (idx, raw_line) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(ConfigError::Malformed { line: line_no })?;
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "bus" => {
+                    config.bus = parse_config_u8(value).ok_or_else(|| ConfigError::InvalidValue {
+                        key: key.to_owned(),
+                        line: line_no,
+                        value: value.to_owned(),
+                    })?;
+                }
+                "addr" => {
+                    config.address =
+                        parse_config_u8(value).ok_or_else(|| ConfigError::InvalidValue {
+                            key: key.to_owned(),
+                            line: line_no,
+                            value: value.to_owned(),
+                        })?;
+                }
+                "hz" => {
+                    config.hz = value.parse().map_err(|_| ConfigError::InvalidValue {
+                        key: key.to_owned(),
+                        line: line_no,
+                        value: value.to_owned(),
+                    })?;
+                }
+                _ => {
+                    return Err(ConfigError::UnknownKey {
+                        key: key.to_owned(),
+                        line: line_no,
+                    })
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn parse_config_u8(value: &str) -> Option<u8> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("malformed config line {line}: expected key=value")]
+    Malformed { line: usize },
+    #[error("unknown config key '{key}' at line {line}")]
+    UnknownKey { key: String, line: usize },
+    #[error("invalid value '{value}' for key '{key}' at line {line}")]
+    InvalidValue {
+        key: String,
+        line: usize,
+        value: String,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -76,7 +284,7 @@ pub enum ImuError {
     #[error("i2c device error: {0}")]
     Bus(#[from] LinuxI2CError),
     #[error("communication error: {0:?}")]
-    Comm(BnoError<LinuxI2CError, ()>),
+    Comm(BnoError<I2cBackendError, ()>),
     #[error("timeout waiting for sensor data")]
     Timeout,
     #[error("sensor reported reset")]
@@ -85,23 +293,33 @@ pub enum ImuError {
     ProductId,
     #[error("protocol error: {0}")]
     Protocol(&'static str),
+    #[error("FRS operation failed with status {0}")]
+    Frs(u8),
+    #[error("gpio i2c backend error: {0}")]
+    Gpio(String),
 }
 
 #[derive(Debug, Default)]
 struct ValueSlot<T> {
     value: Option<T>,
     counter: u64,
+    timestamp_us: i64,
+    accuracy: u8,
 }
 
 impl<T> ValueSlot<T> {
-    fn update(&mut self, value: T, counter: u64) {
+    fn update(&mut self, value: T, counter: u64, timestamp_us: i64, accuracy: u8) {
         self.value = Some(value);
         self.counter = counter;
+        self.timestamp_us = timestamp_us;
+        self.accuracy = accuracy;
     }
 
     fn clear(&mut self) {
         self.value = None;
         self.counter = 0;
+        self.timestamp_us = 0;
+        self.accuracy = 0;
     }
 }
 
@@ -116,6 +334,9 @@ struct SensorState {
     prod_id_verified: bool,
     last_error: Option<u8>,
     pending_reset: bool,
+    frs_write_status: Option<u8>,
+    frs_read_words: Vec<u32>,
+    frs_read_status: Option<u8>,
 }
 
 #[derive(Debug, Default)]
@@ -125,8 +346,271 @@ struct FrameMarkers {
     accel: u64,
 }
 
+#[derive(Debug, Error)]
+pub enum I2cBackendError {
+    #[error("i2c device error: {0}")]
+    Hardware(#[from] LinuxI2CError),
+    #[error("bitbang i2c error: {0}")]
+    Bitbang(String),
+}
+
+/// Dispatches blocking I2C transactions to either the kernel driver or the software
+/// bit-bang implementation, so [`I2cInterface`] (and everything built on it) stays
+/// oblivious to which transport backs the bus.
+pub enum I2cBackend {
+    Hardware(I2cdev),
+    Bitbang(BitbangI2c),
+}
+
+impl I2cBackend {
+    /// Opens the transport selected by `kind`. `device_path` (e.g. `/dev/i2c-1`) is only
+    /// used for the hardware backend.
+    pub fn open(kind: &I2cBackendKind, device_path: &str) -> Result<Self, ImuError> {
+        match kind {
+            I2cBackendKind::Hardware => Ok(Self::Hardware(I2cdev::new(device_path)?)),
+            I2cBackendKind::Bitbang { scl_pin, sda_pin } => {
+                let bitbang = BitbangI2c::new(*scl_pin, *sda_pin).map_err(ImuError::Gpio)?;
+                Ok(Self::Bitbang(bitbang))
+            }
+        }
+    }
+}
+
+impl I2cRead for I2cBackend {
+    type Error = I2cBackendError;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        match self {
+            Self::Hardware(dev) => dev.read(address, buffer).map_err(I2cBackendError::Hardware),
+            Self::Bitbang(bb) => bb.read(address, buffer).map_err(I2cBackendError::Bitbang),
+        }
+    }
+}
+
+impl I2cWrite for I2cBackend {
+    type Error = I2cBackendError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        match self {
+            Self::Hardware(dev) => dev.write(address, bytes).map_err(I2cBackendError::Hardware),
+            Self::Bitbang(bb) => bb.write(address, bytes).map_err(I2cBackendError::Bitbang),
+        }
+    }
+}
+
+impl I2cWriteRead for I2cBackend {
+    type Error = I2cBackendError;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        match self {
+            Self::Hardware(dev) => dev
+                .write_read(address, bytes, buffer)
+                .map_err(I2cBackendError::Hardware),
+            Self::Bitbang(bb) => {
+                bb.write(address, bytes).map_err(I2cBackendError::Bitbang)?;
+                bb.read(address, buffer).map_err(I2cBackendError::Bitbang)
+            }
+        }
+    }
+}
+
+const BITBANG_CLOCK_HZ: u32 = 100_000;
+const CLOCK_STRETCH_TIMEOUT: Duration = Duration::from_millis(25);
+
+/// Software (bit-banged) I2C master driving SCL/SDA as raw GPIO lines, for boards where
+/// the kernel I2C controller is flaky or its driver isn't available. Both lines are
+/// treated as open-drain: a `1` is "released" by switching the pin to an input (relying
+/// on the bus pull-up), a `0` is driven low as an output. The BNO08x is known to stretch
+/// the clock during reads, so raising SCL releases it and then waits (up to
+/// [`CLOCK_STRETCH_TIMEOUT`]) for the line to actually read high before proceeding,
+/// rather than assuming the master's release alone is enough.
+pub struct BitbangI2c {
+    scl: GpioPin,
+    sda: GpioPin,
+    half_period: Duration,
+}
+
+impl BitbangI2c {
+    pub fn new(scl_pin: u64, sda_pin: u64) -> Result<Self, String> {
+        let scl = GpioPin::new(scl_pin);
+        let sda = GpioPin::new(sda_pin);
+        scl.export().map_err(|err| format!("failed to export GPIO{scl_pin}: {err}"))?;
+        sda.export().map_err(|err| format!("failed to export GPIO{sda_pin}: {err}"))?;
+        scl.set_direction(GpioDirection::In)
+            .map_err(|err| format!("failed to configure GPIO{scl_pin} as input: {err}"))?;
+        sda.set_direction(GpioDirection::In)
+            .map_err(|err| format!("failed to configure GPIO{sda_pin} as input: {err}"))?;
+        let half_period_ns = 1_000_000_000u64 / (2 * u64::from(BITBANG_CLOCK_HZ));
+        Ok(Self {
+            scl,
+            sda,
+            half_period: Duration::from_nanos(half_period_ns),
+        })
+    }
+
+    fn delay(&self) {
+        thread::sleep(self.half_period);
+    }
+
+    fn release_sda(&mut self) -> Result<(), String> {
+        self.sda
+            .set_direction(GpioDirection::In)
+            .map_err(|err| format!("gpio error releasing SDA: {err}"))
+    }
+
+    fn drive_sda_low(&mut self) -> Result<(), String> {
+        self.sda
+            .set_direction(GpioDirection::Out)
+            .map_err(|err| format!("gpio error driving SDA: {err}"))?;
+        OutputPin::set_low(&mut self.sda)
+            .map_err(|err| format!("gpio error driving SDA low: {err:?}"))
+    }
+
+    fn read_sda(&self) -> Result<bool, String> {
+        InputPin::is_high(&self.sda).map_err(|err| format!("gpio error reading SDA: {err:?}"))
+    }
+
+    fn release_scl(&mut self) -> Result<(), String> {
+        self.scl
+            .set_direction(GpioDirection::In)
+            .map_err(|err| format!("gpio error releasing SCL: {err}"))
+    }
+
+    fn drive_scl_low(&mut self) -> Result<(), String> {
+        self.scl
+            .set_direction(GpioDirection::Out)
+            .map_err(|err| format!("gpio error driving SCL: {err}"))?;
+        OutputPin::set_low(&mut self.scl)
+            .map_err(|err| format!("gpio error driving SCL low: {err:?}"))
+    }
+
+    fn read_scl(&self) -> Result<bool, String> {
+        InputPin::is_high(&self.scl).map_err(|err| format!("gpio error reading SCL: {err:?}"))
+    }
+
+    /// Raises SCL (by releasing it) and waits for it to actually read high, so a slave
+    /// stretching the clock by holding it low delays the master instead of being ignored.
+    fn set_scl(&mut self, high: bool) -> Result<(), String> {
+        if !high {
+            return self.drive_scl_low();
+        }
+        self.release_scl()?;
+        let deadline = Instant::now() + CLOCK_STRETCH_TIMEOUT;
+        while !self.read_scl()? {
+            if Instant::now() >= deadline {
+                return Err("timed out waiting for SCL to release (clock stretch)".to_owned());
+            }
+            self.delay();
+        }
+        Ok(())
+    }
+
+    fn start(&mut self) -> Result<(), String> {
+        self.release_sda()?;
+        self.set_scl(true)?;
+        self.delay();
+        self.drive_sda_low()?;
+        self.delay();
+        self.set_scl(false)?;
+        self.delay();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), String> {
+        self.drive_sda_low()?;
+        self.delay();
+        self.set_scl(true)?;
+        self.delay();
+        self.release_sda()?;
+        self.delay();
+        Ok(())
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), String> {
+        if bit {
+            self.release_sda()?;
+        } else {
+            self.drive_sda_low()?;
+        }
+        self.delay();
+        self.set_scl(true)?;
+        self.delay();
+        self.set_scl(false)?;
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, String> {
+        self.release_sda()?;
+        self.delay();
+        self.set_scl(true)?;
+        self.delay();
+        let bit = self.read_sda()?;
+        self.set_scl(false)?;
+        Ok(bit)
+    }
+
+    /// Writes one byte MSB-first and returns whether the slave ACKed it.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, String> {
+        for i in (0..8).rev() {
+            self.write_bit((byte >> i) & 1 == 1)?;
+        }
+        Ok(!self.read_bit()?)
+    }
+
+    /// Reads one byte MSB-first, sending `ack` (low = ACK, continue reading) afterwards.
+    fn read_byte(&mut self, ack: bool) -> Result<u8, String> {
+        let mut byte = 0u8;
+        for _ in 0..8 {
+            byte = (byte << 1) | u8::from(self.read_bit()?);
+        }
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+}
+
+impl I2cRead for BitbangI2c {
+    type Error = String;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), String> {
+        self.start()?;
+        if !self.write_byte((address << 1) | 1)? {
+            self.stop()?;
+            return Err(format!("no ACK from device 0x{address:02X}"));
+        }
+        let last = buffer.len().saturating_sub(1);
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = self.read_byte(i != last)?;
+        }
+        self.stop()
+    }
+}
+
+impl I2cWrite for BitbangI2c {
+    type Error = String;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), String> {
+        self.start()?;
+        if !self.write_byte(address << 1)? {
+            self.stop()?;
+            return Err(format!("no ACK from device 0x{address:02X}"));
+        }
+        for &byte in bytes {
+            if !self.write_byte(byte)? {
+                self.stop()?;
+                return Err(format!("no ACK for data byte 0x{byte:02X}"));
+            }
+        }
+        self.stop()
+    }
+}
+
 pub struct Imu {
-    iface: I2cInterface<I2cdev>,
+    iface: I2cInterface<I2cBackend>,
     delay: Delay,
     seq_numbers: [u8; NUM_CHANNELS],
     recv_buf: [u8; PACKET_RECV_BUF_LEN],
@@ -135,14 +619,18 @@ pub struct Imu {
     state: SensorState,
     last_frame: FrameMarkers,
     config: ImuConfig,
-    start: Instant,
+    timebase_us: i64,
+    origin_timebase_us: Option<i64>,
+    cmd_seq: u8,
+    queue: VecDeque<Frame>,
+    event_queue: VecDeque<ImuEvent>,
 }
 
 impl Imu {
     pub fn init(config: ImuConfig) -> Result<Self, ImuError> {
         let device_path = config.device_path();
-        let dev = I2cdev::new(device_path)?;
-        let iface = I2cInterface::new(dev, config.address);
+        let backend = I2cBackend::open(&config.i2c, &device_path)?;
+        let iface = I2cInterface::new(backend, config.address);
         let mut imu = Self {
             iface,
             delay: Delay,
@@ -153,7 +641,11 @@ impl Imu {
             state: SensorState::default(),
             last_frame: FrameMarkers::default(),
             config,
-            start: Instant::now(),
+            timebase_us: 0,
+            origin_timebase_us: None,
+            cmd_seq: 0,
+            queue: VecDeque::with_capacity(FRAME_QUEUE_CAPACITY),
+            event_queue: VecDeque::with_capacity(EVENT_QUEUE_CAPACITY),
         };
         imu.bootstrap()?;
         imu.enable_reports(imu.config.hz)?;
@@ -168,12 +660,20 @@ impl Imu {
         self.send_feature_command(SENSOR_REPORTID_LINEAR_ACCELERATION, interval_us)?;
         self.send_feature_command(SENSOR_REPORTID_ACCELEROMETER, interval_us)?;
         self.send_feature_command(SENSOR_REPORTID_GYRO_CALIBRATED, interval_us)?;
+        self.send_feature_command(SENSOR_REPORTID_TAP_DETECTOR, interval_us)?;
+        self.send_feature_command(SENSOR_REPORTID_STEP_DETECTOR, interval_us)?;
+        self.send_feature_command(SENSOR_REPORTID_STEP_COUNTER, interval_us)?;
+        self.send_feature_command(SENSOR_REPORTID_STABILITY_CLASSIFIER, interval_us)?;
+        self.send_feature_command(SENSOR_REPORTID_PERSONAL_ACTIVITY_CLASSIFIER, interval_us)?;
         Ok(())
     }
 
     pub fn poll_frame(&mut self, timeout: Duration) -> Result<Frame, ImuError> {
         let deadline = Instant::now() + timeout;
         loop {
+            if let Some(frame) = self.try_next_frame() {
+                return Ok(frame);
+            }
             let now = Instant::now();
             if now >= deadline {
                 return Err(ImuError::Timeout);
@@ -188,12 +688,56 @@ impl Imu {
                     return Err(ImuError::SensorReset);
                 }
                 if let Some(frame) = self.try_build_frame() {
-                    return Ok(frame);
+                    self.push_frame(frame);
                 }
             }
         }
     }
 
+    /// Drains every packet currently pending on the bus (zero-timeout reads), assembling
+    /// as many frames as are available and returning them in one batch. Lets a caller
+    /// service many buffered samples per syscall instead of one I2C read per frame.
+    pub fn drain(&mut self) -> Result<SmallVec<[Frame; FRAME_QUEUE_CAPACITY]>, ImuError> {
+        loop {
+            let handled = self.handle_one_message(0)?;
+            if handled == 0 {
+                break;
+            }
+            if self.state.pending_reset {
+                self.state.pending_reset = false;
+                return Err(ImuError::SensorReset);
+            }
+            if let Some(frame) = self.try_build_frame() {
+                self.push_frame(frame);
+            }
+        }
+        Ok(self.queue.drain(..).collect())
+    }
+
+    /// Pops the oldest buffered frame without touching the bus.
+    pub fn try_next_frame(&mut self) -> Option<Frame> {
+        self.queue.pop_front()
+    }
+
+    /// Pops the oldest buffered on-chip gesture/classifier event, if any.
+    pub fn poll_event(&mut self) -> Option<ImuEvent> {
+        self.event_queue.pop_front()
+    }
+
+    fn push_event(&mut self, event: ImuEvent) {
+        if self.event_queue.len() >= EVENT_QUEUE_CAPACITY {
+            self.event_queue.pop_front();
+        }
+        self.event_queue.push_back(event);
+    }
+
+    fn push_frame(&mut self, frame: Frame) {
+        if self.queue.len() >= FRAME_QUEUE_CAPACITY {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(frame);
+    }
+
     pub fn handle_reset(&mut self) -> Result<(), ImuError> {
         self.bootstrap()?;
         self.enable_reports(self.config.hz)?;
@@ -205,11 +749,123 @@ impl Imu {
         self.state.last_error
     }
 
+    /// Enables (or disables) continuous motion-engine calibration for the given sensors.
+    pub fn configure_calibration(
+        &mut self,
+        accel: bool,
+        gyro: bool,
+        mag: bool,
+    ) -> Result<(), ImuError> {
+        let params = [accel as u8, gyro as u8, mag as u8, 0, 0, 0, 0, 0, 0];
+        self.send_command(SH2_CMD_ME_CALIBRATION, params)
+    }
+
+    /// Persists the current dynamic calibration data (DCD) to non-volatile storage.
+    pub fn save_dcd(&mut self) -> Result<(), ImuError> {
+        self.send_command(SH2_CMD_SAVE_DCD, [0; 9])
+    }
+
+    /// Reads a Flash Record System entry, returning its words in record order.
+    pub fn read_frs(&mut self, record_id: u16) -> Result<Vec<u32>, ImuError> {
+        self.state.frs_read_words.clear();
+        self.state.frs_read_status = None;
+        let req = [
+            SHUB_FRS_READ_REQUEST,
+            0,
+            0,
+            0,
+            (record_id & 0xFF) as u8,
+            ((record_id >> 8) & 0xFF) as u8,
+            0,
+            0,
+        ];
+        self.send_packet(CHANNEL_HUB_CONTROL, &req)?;
+        let start = Instant::now();
+        loop {
+            let handled = self.handle_one_message(150)?;
+            match self.state.frs_read_status {
+                Some(FRS_READ_STATUS_NO_MORE_DATA)
+                | Some(FRS_READ_STATUS_COMPLETE)
+                | Some(FRS_READ_STATUS_BLOCK_COMPLETE)
+                | Some(FRS_READ_STATUS_RECORD_EMPTY) => break,
+                Some(FRS_READ_STATUS_BUSY) | None => {}
+                Some(status) => return Err(ImuError::Frs(status)),
+            }
+            if handled == 0 && start.elapsed() > Duration::from_secs(2) {
+                return Err(ImuError::Timeout);
+            }
+        }
+        Ok(std::mem::take(&mut self.state.frs_read_words))
+    }
+
+    /// Writes a Flash Record System entry, two words per FRS-write-data packet.
+    pub fn write_frs(&mut self, record_id: u16, words: &[u32]) -> Result<(), ImuError> {
+        self.state.frs_write_status = None;
+        let req = [
+            SHUB_FRS_WRITE_REQUEST,
+            0,
+            (words.len() & 0xFF) as u8,
+            ((words.len() >> 8) & 0xFF) as u8,
+            (record_id & 0xFF) as u8,
+            ((record_id >> 8) & 0xFF) as u8,
+        ];
+        self.send_packet(CHANNEL_HUB_CONTROL, &req)?;
+        self.await_frs_write_status(&[FRS_WRITE_STATUS_READY])?;
+
+        let last_chunk = words.chunks(2).count().saturating_sub(1);
+        for (chunk_index, chunk) in words.chunks(2).enumerate() {
+            let offset = (chunk_index * 2) as u16;
+            let mut body = vec![
+                SHUB_FRS_WRITE_DATA,
+                0,
+                (offset & 0xFF) as u8,
+                ((offset >> 8) & 0xFF) as u8,
+            ];
+            for word in chunk {
+                body.extend_from_slice(&word.to_le_bytes());
+            }
+            if chunk.len() == 1 {
+                body.extend_from_slice(&[0, 0, 0, 0]);
+            }
+            self.send_packet(CHANNEL_HUB_CONTROL, &body)?;
+            // The hub answers every chunk with "received"; only the last chunk may
+            // additionally (or instead) answer with "write completed".
+            if chunk_index == last_chunk {
+                self.await_frs_write_status(&[FRS_WRITE_STATUS_RECEIVED, FRS_WRITE_STATUS_COMPLETED])?;
+            } else {
+                self.await_frs_write_status(&[FRS_WRITE_STATUS_RECEIVED])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn await_frs_write_status(&mut self, expected: &[u8]) -> Result<(), ImuError> {
+        self.state.frs_write_status = None;
+        let start = Instant::now();
+        loop {
+            let handled = self.handle_one_message(150)?;
+            if let Some(status) = self.state.frs_write_status {
+                if expected.contains(&status) {
+                    return Ok(());
+                }
+                return Err(ImuError::Frs(status));
+            }
+            if handled == 0 && start.elapsed() > Duration::from_secs(2) {
+                return Err(ImuError::Timeout);
+            }
+        }
+    }
+
     fn bootstrap(&mut self) -> Result<(), ImuError> {
         self.seq_numbers = [0; NUM_CHANNELS];
         self.state = SensorState::default();
         self.last_frame = FrameMarkers::default();
         self.report_counter = 0;
+        self.timebase_us = 0;
+        self.origin_timebase_us = None;
+        self.cmd_seq = 0;
+        self.queue.clear();
+        self.event_queue.clear();
         self.iface.setup(&mut self.delay)?;
         self.delay.delay_ms(1u8);
         self.soft_reset()?;
@@ -219,7 +875,6 @@ impl Imu {
         self.eat_all_messages(50)?;
         self.verify_product_id()?;
         self.state.pending_reset = false;
-        self.start = Instant::now();
         Ok(())
     }
 
@@ -251,6 +906,7 @@ impl Imu {
         self.state.gyro.clear();
         self.state.quat.clear();
         self.last_frame = FrameMarkers::default();
+        self.origin_timebase_us = None;
     }
 
     fn try_build_frame(&mut self) -> Option<Frame> {
@@ -271,11 +927,20 @@ impl Imu {
         }
         let quat = quat_slot.value.unwrap();
         let gyro = gyro_slot.value.unwrap();
+        let (accel_timestamp_us, accel_accuracy) =
+            self.select_accel_timestamp_and_accuracy(accel_counter);
+        let sample_timestamp_us = quat_slot
+            .timestamp_us
+            .max(gyro_slot.timestamp_us)
+            .max(accel_timestamp_us);
+        let origin_us = *self.origin_timebase_us.get_or_insert(sample_timestamp_us);
+        let t = ((sample_timestamp_us - origin_us) as f64) / 1_000_000.0;
+        let accuracy = quat_slot.accuracy.min(gyro_slot.accuracy).min(accel_accuracy);
         self.last_frame.quat = quat_slot.counter;
         self.last_frame.gyro = gyro_slot.counter;
         self.last_frame.accel = accel_counter;
         Some(Frame {
-            t: self.start.elapsed().as_secs_f64(),
+            t,
             ax: accel_value[0],
             ay: accel_value[1],
             az: accel_value[2],
@@ -286,6 +951,7 @@ impl Imu {
             qi: quat[1],
             qj: quat[2],
             qk: quat[3],
+            accuracy,
         })
     }
 
@@ -306,6 +972,26 @@ impl Imu {
         }
     }
 
+    fn select_accel_timestamp_and_accuracy(&self, accel_counter: u64) -> (i64, u8) {
+        if self.state.linear_accel.counter == accel_counter {
+            (
+                self.state.linear_accel.timestamp_us,
+                self.state.linear_accel.accuracy,
+            )
+        } else {
+            (self.state.accel.timestamp_us, self.state.accel.accuracy)
+        }
+    }
+
+    fn send_command(&mut self, command: u8, params: [u8; 9]) -> Result<(), ImuError> {
+        let seq = self.cmd_seq;
+        self.cmd_seq = self.cmd_seq.wrapping_add(1);
+        let mut body = vec![SHUB_COMMAND_REQ, seq, command];
+        body.extend_from_slice(&params);
+        self.send_packet(CHANNEL_HUB_CONTROL, &body)?;
+        Ok(())
+    }
+
     fn send_feature_command(&mut self, report_id: u8, interval_us: u32) -> Result<(), ImuError> {
         let body = [
             SHUB_REPORT_SET_FEATURE_CMD,
@@ -447,14 +1133,50 @@ impl Imu {
                 self.state.prod_id_verified = true;
                 Ok(())
             }
+            SHUB_FRS_WRITE_RESPONSE => {
+                if received_len > PACKET_HEADER_LENGTH + 1 {
+                    self.state.frs_write_status = Some(self.recv_buf[PACKET_HEADER_LENGTH + 1]);
+                }
+                Ok(())
+            }
+            SHUB_FRS_READ_RESPONSE => {
+                self.handle_frs_read_response(received_len);
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
 
+    fn handle_frs_read_response(&mut self, received_len: usize) {
+        if received_len < PACKET_HEADER_LENGTH + 14 {
+            return;
+        }
+        let body = &self.recv_buf[PACKET_HEADER_LENGTH..received_len];
+        let status_and_len = body[1];
+        let word_count = status_and_len >> 4;
+        let status = status_and_len & 0x0F;
+        let words = [
+            u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+            u32::from_le_bytes([body[8], body[9], body[10], body[11]]),
+        ];
+        for word in words.iter().take(word_count as usize) {
+            self.state.frs_read_words.push(*word);
+        }
+        self.state.frs_read_status = Some(status);
+    }
+
     fn handle_sensor_reports(&mut self, received_len: usize) -> Result<(), ImuError> {
         if received_len <= PACKET_HEADER_LENGTH + 5 {
             return Ok(());
         }
+        if self.recv_buf[PACKET_HEADER_LENGTH] == SENSOR_REPORT_BASE_TIMESTAMP {
+            let base = &self.recv_buf[PACKET_HEADER_LENGTH + 1..PACKET_HEADER_LENGTH + 5];
+            let raw = u32::from_le_bytes([base[0], base[1], base[2], base[3]]);
+            // The hub emits this as a rolling 32-bit delta (in 100 us ticks) since the
+            // previous reference; reinterpreting as signed handles the periodic wrap.
+            let delta = raw as i32;
+            self.timebase_us += i64::from(delta) * TIMESTAMP_TICK_US;
+        }
         let mut cursor = PACKET_HEADER_LENGTH + 5;
         while cursor < received_len {
             if cursor + 4 > received_len {
@@ -462,8 +1184,13 @@ impl Imu {
             }
             let feature_report_id = self.recv_buf[cursor];
             let _seq = self.recv_buf[cursor + 1];
-            let _status = self.recv_buf[cursor + 2];
-            let _delay = self.recv_buf[cursor + 3];
+            let status = self.recv_buf[cursor + 2];
+            let delay_lsb = self.recv_buf[cursor + 3];
+            // Top two bits of the status byte are the high bits of the 10-bit delay field;
+            // the bottom two bits carry the motion-engine calibration accuracy (0-3).
+            let delay_ticks = u16::from(delay_lsb) | (u16::from(status & 0xC0) << 2);
+            let report_timestamp_us = self.timebase_us + i64::from(delay_ticks) * TIMESTAMP_TICK_US;
+            let accuracy = status & 0x03;
             cursor += 4;
             let remaining = received_len.saturating_sub(cursor);
             let packet = &self.recv_buf[..received_len];
@@ -496,7 +1223,9 @@ impl Imu {
                         q14_to_f32(qj),
                         q14_to_f32(qk),
                     ];
-                    self.state.quat.update(quat, self.report_counter);
+                    self.state
+                        .quat
+                        .update(quat, self.report_counter, report_timestamp_us, accuracy);
                 }
                 SENSOR_REPORTID_LINEAR_ACCELERATION => {
                     if remaining < 6 {
@@ -509,6 +1238,8 @@ impl Imu {
                     self.state.linear_accel.update(
                         [q8_to_f32(ax), q8_to_f32(ay), q8_to_f32(az)],
                         self.report_counter,
+                        report_timestamp_us,
+                        accuracy,
                     );
                 }
                 SENSOR_REPORTID_ACCELEROMETER => {
@@ -522,6 +1253,8 @@ impl Imu {
                     self.state.accel.update(
                         [q8_to_f32(ax), q8_to_f32(ay), q8_to_f32(az)],
                         self.report_counter,
+                        report_timestamp_us,
+                        accuracy,
                     );
                 }
                 SENSOR_REPORTID_GYRO_CALIBRATED => {
@@ -535,8 +1268,58 @@ impl Imu {
                     self.state.gyro.update(
                         [q9_to_f32(gx), q9_to_f32(gy), q9_to_f32(gz)],
                         self.report_counter,
+                        report_timestamp_us,
+                        accuracy,
                     );
                 }
+                SENSOR_REPORTID_TAP_DETECTOR => {
+                    if remaining < 1 {
+                        break;
+                    }
+                    let bits = packet[idx];
+                    idx += 1;
+                    self.bump_report_counter();
+                    self.push_event(ImuEvent::Tap(TapEvent::from_bitfield(bits)));
+                }
+                SENSOR_REPORTID_STEP_DETECTOR => {
+                    self.bump_report_counter();
+                    self.push_event(ImuEvent::Step);
+                }
+                SENSOR_REPORTID_STEP_COUNTER => {
+                    if remaining < 5 {
+                        break;
+                    }
+                    // Bytes 0-2 are reserved/latency; the u16 step count sits at offset 3.
+                    let count = u16::from_le_bytes([packet[idx + 3], packet[idx + 4]]);
+                    idx += 5;
+                    self.bump_report_counter();
+                    self.push_event(ImuEvent::StepCount(count));
+                }
+                SENSOR_REPORTID_STABILITY_CLASSIFIER => {
+                    if remaining < 1 {
+                        break;
+                    }
+                    let value = packet[idx];
+                    idx += 1;
+                    self.bump_report_counter();
+                    self.push_event(ImuEvent::Stability(StabilityState::from_report_value(value)));
+                }
+                SENSOR_REPORTID_PERSONAL_ACTIVITY_CLASSIFIER => {
+                    if remaining < 2 + ACTIVITY_STATE_COUNT {
+                        break;
+                    }
+                    // byte 0 is a page-number/EOS byte, not part of the classifier payload.
+                    idx += 1;
+                    let most_likely = packet[idx];
+                    idx += 1;
+                    let confidences = packet[idx..idx + ACTIVITY_STATE_COUNT].to_vec();
+                    idx += ACTIVITY_STATE_COUNT;
+                    self.bump_report_counter();
+                    self.push_event(ImuEvent::Activity(ActivityEvent {
+                        most_likely,
+                        confidences,
+                    }));
+                }
                 _ => {}
             }
             cursor = idx;
@@ -564,8 +1347,8 @@ fn q14_to_f32(val: i16) -> f32 {
     val as f32 * Q14_SCALE
 }
 
-impl From<BnoError<LinuxI2CError, ()>> for ImuError {
-    fn from(err: BnoError<LinuxI2CError, ()>) -> Self {
+impl From<BnoError<I2cBackendError, ()>> for ImuError {
+    fn from(err: BnoError<I2cBackendError, ()>) -> Self {
         Self::Comm(err)
     }
 }
@@ -1,14 +1,15 @@
 use anyhow::{bail, Context, Result};
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use ctrlc;
 use embedded_hal::blocking::i2c::Read as I2cRead;
 use env_logger::Env;
-use imu_rust::{Imu, ImuConfig, ImuError};
-use linux_embedded_hal::I2cdev;
-use log::{error, info, warn, LevelFilter};
-use std::io::{self, Write};
+use imu_rust::{Frame, I2cBackend, I2cBackendKind, Imu, ImuConfig, ImuError};
+use log::{error, info, warn, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -16,10 +17,20 @@ const MAX_FRAME: usize = 512;
 const INITIAL_CHECK_DELAY_MS: u64 = 200;
 const METRICS_INTERVAL: Duration = Duration::from_secs(5);
 const FLUSH_INTERVAL: usize = 1;
+const DEFAULT_CONFIG_PATH: &str = "/etc/imu.conf";
+const LOG_RING_CAPACITY: usize = 256;
 
 #[derive(Debug, Parser)]
 #[command(name = "imu", version, about = "BNO085 tooling")]
 struct Cli {
+    #[arg(
+        long,
+        global = true,
+        default_value = DEFAULT_CONFIG_PATH,
+        help = "Path to a key=value config file providing defaults for flags below"
+    )]
+    config: String,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -30,6 +41,10 @@ enum Command {
     Check(CheckArgs),
     /// Strumień CSV z pełnej obsługi IMU (dawne imu_read)
     Read(ReadArgs),
+    /// Capture a live session to a binary recording file
+    Record(RecordArgs),
+    /// Replay a recording made with `record`, honoring its original timing
+    Replay(ReplayArgs),
 }
 
 #[derive(Debug, Args)]
@@ -55,6 +70,20 @@ struct CheckArgs {
         help = "Opóźnienie (ms) pomiędzy kolejnymi odczytami ramek"
     )]
     wait_ms: u64,
+
+    #[arg(
+        long,
+        default_value = "hw",
+        value_enum,
+        help = "Transport I2C: sterownik jądra lub software'owy bit-bang po GPIO"
+    )]
+    i2c: I2cMode,
+
+    #[arg(long, help = "Numer linii GPIO dla SCL (wymagany przy --i2c bitbang)")]
+    scl_pin: Option<u64>,
+
+    #[arg(long, help = "Numer linii GPIO dla SDA (wymagany przy --i2c bitbang)")]
+    sda_pin: Option<u64>,
 }
 
 #[derive(Debug, Args)]
@@ -78,6 +107,89 @@ struct ReadArgs {
     )]
     hz: u16,
 
+    #[arg(
+        long,
+        default_value = "hw",
+        value_enum,
+        help = "I2C transport: kernel driver or software bit-bang over GPIO"
+    )]
+    i2c: I2cMode,
+
+    #[arg(long, help = "GPIO line number for SCL (required when --i2c bitbang)")]
+    scl_pin: Option<u64>,
+
+    #[arg(long, help = "GPIO line number for SDA (required when --i2c bitbang)")]
+    sda_pin: Option<u64>,
+
+    #[arg(
+        long,
+        default_value_t = 50u64,
+        value_parser = clap::value_parser!(u64).range(1..=500),
+        help = "Poll timeout in milliseconds"
+    )]
+    timeout_ms: u64,
+
+    #[arg(long, default_value_t = false, help = "Skip printing CSV header")]
+    no_header: bool,
+
+    #[arg(
+        long,
+        default_value = "info",
+        value_enum,
+        help = "Log level for stderr output"
+    )]
+    log_level: LogLevel,
+
+    #[arg(
+        long,
+        default_value = "csv",
+        value_enum,
+        help = "Output encoding: human-readable CSV or framed little-endian binary"
+    )]
+    format: OutputFormat,
+
+    #[arg(
+        long,
+        default_value_t = 0u8,
+        value_parser = clap::value_parser!(u8).range(0..=3),
+        help = "Drop frames below this calibration accuracy (0=unreliable..3=high)"
+    )]
+    min_accuracy: u8,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Binary,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum I2cMode {
+    Hw,
+    Bitbang,
+}
+
+#[derive(Debug, Args)]
+struct RecordArgs {
+    #[arg(long, default_value_t = 1u8, help = "I2C bus number (default 1)")]
+    bus: u8,
+
+    #[arg(
+        long,
+        default_value = "0x4A",
+        value_parser = parse_hex_u8,
+        help = "I2C address in hex (default 0x4A)"
+    )]
+    addr: u8,
+
+    #[arg(
+        long,
+        default_value_t = 100u16,
+        value_parser = clap::value_parser!(u16).range(1..=400),
+        help = "Target sampling frequency in Hz"
+    )]
+    hz: u16,
+
     #[arg(
         long,
         default_value_t = 50u64,
@@ -86,6 +198,34 @@ struct ReadArgs {
     )]
     timeout_ms: u64,
 
+    #[arg(
+        long,
+        default_value = "info",
+        value_enum,
+        help = "Log level for stderr output"
+    )]
+    log_level: LogLevel,
+
+    #[arg(long, help = "Path to write the recorded binary session to")]
+    output: String,
+}
+
+#[derive(Debug, Args)]
+struct ReplayArgs {
+    #[arg(long, help = "Path to a recording produced by `record`")]
+    input: String,
+
+    #[arg(
+        long,
+        default_value_t = 1.0,
+        value_parser = parse_speed,
+        help = "Playback speed multiplier (2.0 = twice as fast, 0.5 = half speed)"
+    )]
+    speed: f64,
+
+    #[arg(long = "loop", default_value_t = false, help = "Loop the recording continuously")]
+    loop_playback: bool,
+
     #[arg(long, default_value_t = false, help = "Skip printing CSV header")]
     no_header: bool,
 
@@ -118,17 +258,173 @@ impl From<LogLevel> for LevelFilter {
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = match Cli::from_arg_matches(&matches) {
+        Ok(cli) => cli,
+        Err(err) => err.exit(),
+    };
+    let file_config = FileConfig::load(&cli.config);
+
     match cli.command {
-        Command::Check(args) => run_check(args),
-        Command::Read(args) => run_read(args),
+        Command::Check(mut args) => {
+            if let Some(sub_matches) = matches.subcommand_matches("check") {
+                apply_check_overrides(&mut args, &file_config, sub_matches);
+            }
+            run_check(args)
+        }
+        Command::Read(mut args) => {
+            if let Some(sub_matches) = matches.subcommand_matches("read") {
+                apply_read_overrides(&mut args, &file_config, sub_matches);
+            }
+            run_read(args)
+        }
+        Command::Record(args) => run_record(args),
+        Command::Replay(args) => run_replay(args),
+    }
+}
+
+/// Defaults loaded from a `key=value` config file (inspired by the ARTIQ SD-card
+/// `config.txt` scheme), applied to `read` and `check` flags the user didn't pass explicitly
+/// (`check` only takes `bus` and `addr`). `record` and `replay` do not read these defaults.
+#[derive(Debug, Default)]
+struct FileConfig {
+    bus: Option<u8>,
+    addr: Option<u8>,
+    hz: Option<u16>,
+    timeout_ms: Option<u64>,
+    log_level: Option<LogLevel>,
+    no_header: Option<bool>,
+}
+
+impl FileConfig {
+    fn load(path: &str) -> Self {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                eprintln!("warning: failed to read config file {path}: {err}");
+                return Self::default();
+            }
+        };
+        Self::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut config = Self::default();
+        for (idx, raw_line) in contents.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                eprintln!("warning: ignoring malformed config line {line_no}: {line}");
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "bus" => match value.parse() {
+                    Ok(v) => config.bus = Some(v),
+                    Err(_) => eprintln!("warning: invalid bus value on line {line_no}: {value}"),
+                },
+                "addr" => match parse_hex_u8(value) {
+                    Ok(v) => config.addr = Some(v),
+                    Err(err) => eprintln!("warning: invalid addr value on line {line_no}: {err}"),
+                },
+                "hz" => match value.parse::<u16>() {
+                    Ok(v) if (1..=400).contains(&v) => config.hz = Some(v),
+                    Ok(v) => eprintln!(
+                        "warning: hz value {v} on line {line_no} is out of range 1..=400, ignoring"
+                    ),
+                    Err(_) => eprintln!("warning: invalid hz value on line {line_no}: {value}"),
+                },
+                "timeout_ms" => match value.parse::<u64>() {
+                    Ok(v) if (1..=500).contains(&v) => config.timeout_ms = Some(v),
+                    Ok(v) => eprintln!(
+                        "warning: timeout_ms value {v} on line {line_no} is out of range 1..=500, ignoring"
+                    ),
+                    Err(_) => {
+                        eprintln!("warning: invalid timeout_ms value on line {line_no}: {value}")
+                    }
+                },
+                "log_level" => match value.to_lowercase().as_str() {
+                    "error" => config.log_level = Some(LogLevel::Error),
+                    "warn" => config.log_level = Some(LogLevel::Warn),
+                    "info" => config.log_level = Some(LogLevel::Info),
+                    "debug" => config.log_level = Some(LogLevel::Debug),
+                    _ => {
+                        eprintln!("warning: invalid log_level value on line {line_no}: {value}")
+                    }
+                },
+                "no_header" => match value.parse() {
+                    Ok(v) => config.no_header = Some(v),
+                    Err(_) => {
+                        eprintln!("warning: invalid no_header value on line {line_no}: {value}")
+                    }
+                },
+                _ => eprintln!("warning: ignoring unknown config key '{key}' on line {line_no}"),
+            }
+        }
+        config
+    }
+}
+
+fn was_explicit(matches: &clap::ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+fn apply_check_overrides(args: &mut CheckArgs, file: &FileConfig, matches: &clap::ArgMatches) {
+    if !was_explicit(matches, "bus") {
+        if let Some(bus) = file.bus {
+            args.bus = bus;
+        }
+    }
+    if !was_explicit(matches, "addr") {
+        if let Some(addr) = file.addr {
+            args.addr = addr;
+        }
+    }
+}
+
+fn apply_read_overrides(args: &mut ReadArgs, file: &FileConfig, matches: &clap::ArgMatches) {
+    if !was_explicit(matches, "bus") {
+        if let Some(bus) = file.bus {
+            args.bus = bus;
+        }
+    }
+    if !was_explicit(matches, "addr") {
+        if let Some(addr) = file.addr {
+            args.addr = addr;
+        }
+    }
+    if !was_explicit(matches, "hz") {
+        if let Some(hz) = file.hz {
+            args.hz = hz;
+        }
+    }
+    if !was_explicit(matches, "timeout_ms") {
+        if let Some(timeout_ms) = file.timeout_ms {
+            args.timeout_ms = timeout_ms;
+        }
+    }
+    if !was_explicit(matches, "no_header") {
+        if let Some(no_header) = file.no_header {
+            args.no_header = no_header;
+        }
+    }
+    if !was_explicit(matches, "log_level") {
+        if let Some(log_level) = file.log_level {
+            args.log_level = log_level;
+        }
     }
 }
 
 fn run_check(args: CheckArgs) -> Result<()> {
     let i2c_path = format!("/dev/i2c-{}", args.bus);
-    let mut i2c =
-        I2cdev::new(&i2c_path).with_context(|| format!("nie mogę otworzyć {}", i2c_path))?;
+    let backend_kind = resolve_i2c_backend(args.i2c, args.scl_pin, args.sda_pin)?;
+    let mut i2c = I2cBackend::open(&backend_kind, &i2c_path)
+        .with_context(|| format!("nie mogę otworzyć {}", i2c_path))?;
 
     println!("I2C OK: {}, urządzenie 0x{:02X}", i2c_path, args.addr);
     println!(
@@ -182,12 +478,13 @@ fn run_check(args: CheckArgs) -> Result<()> {
 }
 
 fn run_read(args: ReadArgs) -> Result<()> {
-    init_logging(args.log_level.into());
+    let log_ring = init_logging(args.log_level.into());
 
     let config = ImuConfig {
         bus: args.bus,
         address: args.addr,
         hz: args.hz,
+        i2c: resolve_i2c_backend(args.i2c, args.scl_pin, args.sda_pin)?,
     };
 
     info!(
@@ -200,9 +497,11 @@ fn run_read(args: ReadArgs) -> Result<()> {
     let running = Arc::new(AtomicBool::new(true));
     {
         let running = Arc::clone(&running);
+        let log_ring = Arc::clone(&log_ring);
         ctrlc::set_handler(move || {
             if running.swap(false, Ordering::SeqCst) {
                 warn!("SIGINT received, stopping ...");
+                flush_recent_log(&log_ring);
             }
         })
         .context("failed to install SIGINT handler")?;
@@ -210,7 +509,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
 
     let mut stdout = io::BufWriter::new(io::stdout());
     if !args.no_header {
-        writeln!(stdout, "t,ax,ay,az,gx,gy,gz,qw,qi,qj,qk")?;
+        match args.format {
+            OutputFormat::Csv => write_csv_header(&mut stdout)?,
+            OutputFormat::Binary => write_binary_preamble(&mut stdout)?,
+        }
         stdout.flush()?;
     }
 
@@ -225,6 +527,7 @@ fn run_read(args: ReadArgs) -> Result<()> {
     let mut last_error_msg: Option<String> = None;
     let mut last_flush = 0usize;
     let mut metrics_start = Instant::now();
+    let mut dcd_saved = false;
 
     while running.load(Ordering::SeqCst) {
         if let Some(delay) = next_tick.checked_duration_since(Instant::now()) {
@@ -235,24 +538,27 @@ fn run_read(args: ReadArgs) -> Result<()> {
         next_tick = Instant::now() + interval;
 
         match imu.poll_frame(timeout) {
+            Ok(frame) if frame.accuracy < args.min_accuracy => {
+                drops_total += 1;
+                drops_window += 1;
+                last_error_msg = Some("low_accuracy".to_owned());
+                continue;
+            }
             Ok(frame) => {
                 frames_total += 1;
                 frames_window += 1;
-                write!(
-                    stdout,
-                    "{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}\n",
-                    frame.t,
-                    frame.ax,
-                    frame.ay,
-                    frame.az,
-                    frame.gx,
-                    frame.gy,
-                    frame.gz,
-                    frame.qw,
-                    frame.qi,
-                    frame.qj,
-                    frame.qk
-                )?;
+                if !dcd_saved && frame.accuracy == 3 {
+                    dcd_saved = true;
+                    if let Err(err) = imu.save_dcd() {
+                        warn!("DCD save after calibration convergence failed: {err:?}");
+                    } else {
+                        info!("calibration converged, saved DCD");
+                    }
+                }
+                match args.format {
+                    OutputFormat::Csv => write_csv_frame(&mut stdout, &frame)?,
+                    OutputFormat::Binary => write_binary_frame(&mut stdout, &frame)?,
+                }
                 last_flush += 1;
                 if last_flush >= FLUSH_INTERVAL {
                     stdout.flush()?;
@@ -274,7 +580,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
                     warn!("reset handling failed: {err:?}, attempting full reinit");
                     match recover_imu(&config, &running) {
                         Some(new_imu) => imu = new_imu,
-                        None => break,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
                     }
                 }
                 continue;
@@ -289,7 +598,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
                     warn!("reset after comm error failed: {reset_err:?}, reinitializing");
                     match recover_imu(&config, &running) {
                         Some(new_imu) => imu = new_imu,
-                        None => break,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
                     }
                 }
                 continue;
@@ -301,7 +613,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
                 error!("i2c bus error {err}, reopening");
                 match recover_imu(&config, &running) {
                     Some(new_imu) => imu = new_imu,
-                    None => break,
+                    None => {
+                        flush_recent_log(&log_ring);
+                        break;
+                    }
                 }
                 continue;
             }
@@ -312,7 +627,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
                 warn!("product id verification failed, reinitializing");
                 match recover_imu(&config, &running) {
                     Some(new_imu) => imu = new_imu,
-                    None => break,
+                    None => {
+                        flush_recent_log(&log_ring);
+                        break;
+                    }
                 }
                 continue;
             }
@@ -325,7 +643,10 @@ fn run_read(args: ReadArgs) -> Result<()> {
                     warn!("protocol recovery failed: {err:?}, reinitializing");
                     match recover_imu(&config, &running) {
                         Some(new_imu) => imu = new_imu,
-                        None => break,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
                     }
                 }
                 continue;
@@ -368,11 +689,405 @@ fn run_read(args: ReadArgs) -> Result<()> {
     Ok(())
 }
 
-fn init_logging(level: LevelFilter) {
+fn run_record(args: RecordArgs) -> Result<()> {
+    let log_ring = init_logging(args.log_level.into());
+
+    let config = ImuConfig {
+        bus: args.bus,
+        address: args.addr,
+        hz: args.hz,
+        i2c: I2cBackendKind::Hardware,
+    };
+
+    info!(
+        "starting imu_record bus={} addr=0x{:02X} hz={} timeout_ms={} output={}",
+        config.bus, config.address, config.hz, args.timeout_ms, args.output
+    );
+
+    let mut imu = Imu::init(config.clone()).context("failed to initialize IMU")?;
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = Arc::clone(&running);
+        let log_ring = Arc::clone(&log_ring);
+        ctrlc::set_handler(move || {
+            if running.swap(false, Ordering::SeqCst) {
+                warn!("SIGINT received, stopping ...");
+                flush_recent_log(&log_ring);
+            }
+        })
+        .context("failed to install SIGINT handler")?;
+    }
+
+    let file = std::fs::File::create(&args.output).context("failed to create recording file")?;
+    let mut out = io::BufWriter::new(file);
+    write_binary_preamble(&mut out)?;
+    out.flush()?;
+
+    let interval = Duration::from_secs_f64(1.0 / f64::from(args.hz));
+    let timeout = Duration::from_millis(args.timeout_ms);
+    let mut next_tick = Instant::now();
+
+    let mut frames_total: u64 = 0;
+    let mut drops_total: u64 = 0;
+    let mut last_flush = 0usize;
+
+    while running.load(Ordering::SeqCst) {
+        if let Some(delay) = next_tick.checked_duration_since(Instant::now()) {
+            if delay > Duration::from_micros(200) {
+                thread::sleep(delay);
+            }
+        }
+        next_tick = Instant::now() + interval;
+
+        match imu.poll_frame(timeout) {
+            Ok(frame) => {
+                frames_total += 1;
+                write_binary_frame(&mut out, &frame)?;
+                last_flush += 1;
+                if last_flush >= FLUSH_INTERVAL {
+                    out.flush()?;
+                    last_flush = 0;
+                }
+            }
+            Err(ImuError::Timeout) => {
+                drops_total += 1;
+                continue;
+            }
+            Err(ImuError::SensorReset) => {
+                drops_total += 1;
+                warn!("sensor reported reset, reinitializing");
+                if let Err(err) = imu.handle_reset() {
+                    warn!("reset handling failed: {err:?}, attempting full reinit");
+                    match recover_imu(&config, &running) {
+                        Some(new_imu) => imu = new_imu,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            Err(ImuError::Comm(err)) => {
+                drops_total += 1;
+                warn!("i2c communication error: {err:?}, attempting recovery");
+                thread::sleep(Duration::from_millis(10));
+                if let Err(reset_err) = imu.handle_reset() {
+                    warn!("reset after comm error failed: {reset_err:?}, reinitializing");
+                    match recover_imu(&config, &running) {
+                        Some(new_imu) => imu = new_imu,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            Err(ImuError::Bus(err)) => {
+                drops_total += 1;
+                error!("i2c bus error {err}, reopening");
+                match recover_imu(&config, &running) {
+                    Some(new_imu) => imu = new_imu,
+                    None => {
+                        flush_recent_log(&log_ring);
+                        break;
+                    }
+                }
+                continue;
+            }
+            Err(ImuError::ProductId) => {
+                drops_total += 1;
+                warn!("product id verification failed, reinitializing");
+                match recover_imu(&config, &running) {
+                    Some(new_imu) => imu = new_imu,
+                    None => {
+                        flush_recent_log(&log_ring);
+                        break;
+                    }
+                }
+                continue;
+            }
+            Err(ImuError::Protocol(msg)) => {
+                drops_total += 1;
+                warn!("protocol error ({msg}), attempting recovery");
+                if let Err(err) = imu.handle_reset() {
+                    warn!("protocol recovery failed: {err:?}, reinitializing");
+                    match recover_imu(&config, &running) {
+                        Some(new_imu) => imu = new_imu,
+                        None => {
+                            flush_recent_log(&log_ring);
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+        }
+    }
+
+    out.flush()?;
+    info!(
+        "imu_record stopped: frames_total={} drops_total={} output={}",
+        frames_total, drops_total, args.output
+    );
+    Ok(())
+}
+
+/// Replays a recording made by `record`, honoring the original inter-frame timing (scaled by
+/// `--speed`) by sleeping until a `next_tick` deadline as `run_read` does. Unlike `run_read`,
+/// which restarts the deadline from the current time, replay advances it by each recorded gap.
+fn run_replay(args: ReplayArgs) -> Result<()> {
+    init_logging(args.log_level.into());
+
+    let frames = read_recording(&args.input).context("failed to load recording")?;
+    info!("loaded {} frames from {}", frames.len(), args.input);
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = Arc::clone(&running);
+        ctrlc::set_handler(move || {
+            if running.swap(false, Ordering::SeqCst) {
+                warn!("SIGINT received, stopping ...");
+            }
+        })
+        .context("failed to install SIGINT handler")?;
+    }
+
+    let mut stdout = io::BufWriter::new(io::stdout());
+    if !args.no_header {
+        write_csv_header(&mut stdout)?;
+        stdout.flush()?;
+    }
+
+    let mut last_flush = 0usize;
+    loop {
+        let mut next_tick = Instant::now();
+        let mut prev_t = frames.first().map_or(0.0, |frame| frame.t);
+
+        for frame in &frames {
+            if !running.load(Ordering::SeqCst) {
+                stdout.flush()?;
+                return Ok(());
+            }
+
+            let dt = (frame.t - prev_t).max(0.0) / args.speed;
+            next_tick += Duration::from_secs_f64(dt);
+            if let Some(delay) = next_tick.checked_duration_since(Instant::now()) {
+                thread::sleep(delay);
+            }
+
+            write_csv_frame(&mut stdout, frame)?;
+            last_flush += 1;
+            if last_flush >= FLUSH_INTERVAL {
+                stdout.flush()?;
+                last_flush = 0;
+            }
+            prev_t = frame.t;
+        }
+
+        if !args.loop_playback {
+            break;
+        }
+    }
+
+    stdout.flush()?;
+    info!("imu_replay stopped after {} frames", frames.len());
+    Ok(())
+}
+
+/// Log sink that forwards every record to `env_logger` for normal stderr output while also
+/// keeping the last [`LOG_RING_CAPACITY`] formatted lines in a ring buffer, so a crash handler
+/// can re-print recent history that has already scrolled away. Only records that pass the
+/// same level filter as the stderr output are kept.
+struct BufferLogger {
+    inner: env_logger::Logger,
+    ring: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl Log for BufferLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.enabled(record.metadata()) {
+            let line = format!("[{}] {}: {}", record.level(), record.target(), record.args());
+            if let Ok(mut ring) = self.ring.lock() {
+                if ring.len() >= LOG_RING_CAPACITY {
+                    ring.pop_front();
+                }
+                ring.push_back(line);
+            }
+        }
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Dumps the retained log ring buffer to stderr, prefixed so it's easy to spot after a crash.
+fn flush_recent_log(ring: &Mutex<VecDeque<String>>) {
+    if let Ok(ring) = ring.lock() {
+        eprintln!("--- recent log ---");
+        for line in ring.iter() {
+            eprintln!("{line}");
+        }
+    }
+}
+
+const BINARY_MAGIC: [u8; 4] = *b"IMUB";
+const BINARY_VERSION: u8 = 1;
+const BINARY_TYPE_F64: u8 = 0;
+const BINARY_TYPE_F32: u8 = 1;
+const BINARY_FIELDS: &[(&str, u8)] = &[
+    ("t", BINARY_TYPE_F64),
+    ("ax", BINARY_TYPE_F32),
+    ("ay", BINARY_TYPE_F32),
+    ("az", BINARY_TYPE_F32),
+    ("gx", BINARY_TYPE_F32),
+    ("gy", BINARY_TYPE_F32),
+    ("gz", BINARY_TYPE_F32),
+    ("qw", BINARY_TYPE_F32),
+    ("qi", BINARY_TYPE_F32),
+    ("qj", BINARY_TYPE_F32),
+    ("qk", BINARY_TYPE_F32),
+];
+const BINARY_RECORD_LEN: u16 = 8 + 4 * 10;
+
+/// Writes the one-time preamble for `--format binary`: magic, version, and a field layout
+/// table so downstream readers can self-describe the per-frame record without a shared header.
+fn write_binary_preamble(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(&BINARY_MAGIC)?;
+    out.write_all(&[BINARY_VERSION])?;
+    out.write_all(&[BINARY_FIELDS.len() as u8])?;
+    for (name, type_tag) in BINARY_FIELDS {
+        out.write_all(&[name.len() as u8])?;
+        out.write_all(name.as_bytes())?;
+        out.write_all(&[*type_tag])?;
+    }
+    Ok(())
+}
+
+/// Writes a single frame as `[u16 len][f64 t][f32 ax..qk]`, all little-endian, matching
+/// [`BINARY_FIELDS`]. `len` is the byte length of the record that follows it.
+fn write_binary_frame(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    out.write_all(&BINARY_RECORD_LEN.to_le_bytes())?;
+    out.write_all(&frame.t.to_le_bytes())?;
+    out.write_all(&frame.ax.to_le_bytes())?;
+    out.write_all(&frame.ay.to_le_bytes())?;
+    out.write_all(&frame.az.to_le_bytes())?;
+    out.write_all(&frame.gx.to_le_bytes())?;
+    out.write_all(&frame.gy.to_le_bytes())?;
+    out.write_all(&frame.gz.to_le_bytes())?;
+    out.write_all(&frame.qw.to_le_bytes())?;
+    out.write_all(&frame.qi.to_le_bytes())?;
+    out.write_all(&frame.qj.to_le_bytes())?;
+    out.write_all(&frame.qk.to_le_bytes())?;
+    Ok(())
+}
+
+fn write_csv_header(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "t,ax,ay,az,gx,gy,gz,qw,qi,qj,qk")
+}
+
+fn write_csv_frame(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    write!(
+        out,
+        "{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}\n",
+        frame.t,
+        frame.ax,
+        frame.ay,
+        frame.az,
+        frame.gx,
+        frame.gy,
+        frame.gz,
+        frame.qw,
+        frame.qi,
+        frame.qj,
+        frame.qk
+    )
+}
+
+/// Loads a whole recording into memory up front (per the DMA "cache the handle once, replay
+/// cheaply" idea) so `run_replay` does no per-frame parsing once playback starts.
+fn read_recording(path: &str) -> Result<Vec<Frame>> {
+    let file = std::fs::File::open(path).context("failed to open recording")?;
+    let mut reader = io::BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic).context("truncated recording header")?;
+    if magic != BINARY_MAGIC {
+        bail!("'{path}' is not an imu recording (bad magic)");
+    }
+
+    let mut header = [0u8; 2];
+    reader.read_exact(&mut header).context("truncated recording header")?;
+    let field_count = header[1];
+    for _ in 0..field_count {
+        let mut name_len = [0u8; 1];
+        reader.read_exact(&mut name_len).context("truncated field layout")?;
+        let mut name = vec![0u8; name_len[0] as usize];
+        reader.read_exact(&mut name).context("truncated field layout")?;
+        let mut type_tag = [0u8; 1];
+        reader.read_exact(&mut type_tag).context("truncated field layout")?;
+    }
+
+    let mut frames = Vec::new();
+    loop {
+        let mut len_buf = [0u8; 2];
+        match reader.read_exact(&mut len_buf) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err).context("failed to read recording"),
+        }
+        let len = u16::from_le_bytes(len_buf) as usize;
+        let mut body = vec![0u8; len];
+        reader.read_exact(&mut body).context("truncated recording record")?;
+        frames.push(decode_binary_frame(&body)?);
+    }
+    Ok(frames)
+}
+
+/// Decodes one `[f64 t][f32 ax..qk]` record. Recordings don't carry `accuracy`
+/// (see [`BINARY_FIELDS`]), so replayed frames always report it as unreliable.
+fn decode_binary_frame(body: &[u8]) -> Result<Frame> {
+    if body.len() != BINARY_RECORD_LEN as usize {
+        bail!("unexpected recording record length {}", body.len());
+    }
+    Ok(Frame {
+        t: f64::from_le_bytes(body[0..8].try_into().unwrap()),
+        ax: f32::from_le_bytes(body[8..12].try_into().unwrap()),
+        ay: f32::from_le_bytes(body[12..16].try_into().unwrap()),
+        az: f32::from_le_bytes(body[16..20].try_into().unwrap()),
+        gx: f32::from_le_bytes(body[20..24].try_into().unwrap()),
+        gy: f32::from_le_bytes(body[24..28].try_into().unwrap()),
+        gz: f32::from_le_bytes(body[28..32].try_into().unwrap()),
+        qw: f32::from_le_bytes(body[32..36].try_into().unwrap()),
+        qi: f32::from_le_bytes(body[36..40].try_into().unwrap()),
+        qj: f32::from_le_bytes(body[40..44].try_into().unwrap()),
+        qk: f32::from_le_bytes(body[44..48].try_into().unwrap()),
+        accuracy: 0,
+    })
+}
+
+fn init_logging(level: LevelFilter) -> Arc<Mutex<VecDeque<String>>> {
+    let ring = Arc::new(Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY)));
     let env = Env::default().default_filter_or(level.to_string());
-    env_logger::Builder::from_env(env)
+    let inner = env_logger::Builder::from_env(env)
         .format_timestamp_millis()
-        .init();
+        .build();
+    let max_level = inner.filter();
+    let logger = BufferLogger {
+        inner,
+        ring: Arc::clone(&ring),
+    };
+    log::set_boxed_logger(Box::new(logger)).expect("logger already initialized");
+    log::set_max_level(max_level);
+    ring
 }
 
 fn parse_hex_u8(input: &str) -> Result<u8, String> {
@@ -385,6 +1100,34 @@ fn parse_hex_u8(input: &str) -> Result<u8, String> {
         .map_err(|err| format!("invalid hex byte '{input}': {err}"))
 }
 
+fn parse_speed(input: &str) -> Result<f64, String> {
+    let speed: f64 = input
+        .parse()
+        .map_err(|_| format!("invalid speed '{input}'"))?;
+    if speed > 0.0 && speed.is_finite() {
+        Ok(speed)
+    } else {
+        Err(format!("speed must be a positive, finite number, got '{input}'"))
+    }
+}
+
+/// Turns `--i2c`/`--scl-pin`/`--sda-pin` into an [`I2cBackendKind`], requiring both pins
+/// when bit-bang mode is selected.
+fn resolve_i2c_backend(
+    mode: I2cMode,
+    scl_pin: Option<u64>,
+    sda_pin: Option<u64>,
+) -> Result<I2cBackendKind> {
+    match mode {
+        I2cMode::Hw => Ok(I2cBackendKind::Hardware),
+        I2cMode::Bitbang => {
+            let scl_pin = scl_pin.context("--scl-pin is required when --i2c bitbang")?;
+            let sda_pin = sda_pin.context("--sda-pin is required when --i2c bitbang")?;
+            Ok(I2cBackendKind::Bitbang { scl_pin, sda_pin })
+        }
+    }
+}
+
 fn recover_imu(config: &ImuConfig, running: &Arc<AtomicBool>) -> Option<Imu> {
     let mut backoff = Duration::from_millis(100);
     loop {